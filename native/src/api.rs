@@ -1,28 +1,174 @@
 use crate::db::{
-    initialize_db, import_parquet, execute_query, get_tables_info, 
-    get_indices_info, create_index, QueryResult, TableInfo, IndexInfo
+    init_database as db_init_database, open_connection as db_open_connection,
+    close_connection as db_close_connection, import_parquet, import_file, import_file_into,
+    export_table, export_table_from, execute_query, execute_query_on, execute_query_stringified,
+    run_query_with_params, execute_batch_params, backup_database as db_backup_database,
+    restore_database as db_restore_database, get_database_info, create_index, create_index_on,
+    load_extension, load_extension_on, install_and_load_httpfs, install_and_load_httpfs_on,
+    set_s3_credentials, set_s3_credentials_on, open_cursor, open_cursor_on, fetch_rows as db_fetch_rows,
+    close_cursor as db_close_cursor, BackupProgress, ColumnValue, CursorBatch,
+    DatabaseInfo, ImportFormat, QueryResult, StringifiedQueryResult,
 };
 
-pub fn init_database(db_path: String) -> anyhow::Result<bool> {
-    initialize_db(db_path)
+pub fn init_database(db_path: String) -> anyhow::Result<String> {
+    db_init_database(db_path)
 }
 
-pub fn import_parquet_file(file_path: String, table_name: String) -> anyhow::Result<bool> {
-    import_parquet(file_path, table_name)
+// Open (or re-open) a named connection, letting an app hold several
+// databases open at once instead of serializing every call on one handle.
+pub fn open_connection(name: String, path: String) -> anyhow::Result<bool> {
+    db_open_connection(name, path)
+}
+
+// Close a named connection, invalidating any cursors/statements it held.
+pub fn close_connection(name: String) -> anyhow::Result<bool> {
+    db_close_connection(name)
+}
+
+// Snapshot the live database to the directory at `dst_path` using DuckDB's
+// `EXPORT DATABASE` (schema script plus one data file per table). DuckDB
+// has no single-file incremental backup API to report partial progress
+// from, so `BackupProgress.done` only ever flips to `true` once the whole
+// export has finished.
+pub fn backup_database(dst_path: String) -> anyhow::Result<BackupProgress> {
+    db_backup_database(dst_path)
+}
+
+// Restore the live database from a directory written by `backup_database`.
+pub fn restore_database(src_path: String) -> anyhow::Result<BackupProgress> {
+    db_restore_database(src_path)
+}
+
+pub fn import_parquet_file(file_path: String) -> anyhow::Result<String> {
+    import_parquet(file_path)
+}
+
+// Import a Parquet/CSV/JSON/NDJSON file into `table_name`, dispatching to
+// the matching DuckDB reader. Pass `ImportFormat::Auto` to pick the reader
+// from the file extension.
+pub fn import_file_as(file_path: String, table_name: String, format: ImportFormat) -> anyhow::Result<String> {
+    import_file(file_path, table_name, format)
+}
+
+// Same as `import_file_as`, but against a named connection instead of the
+// default one.
+pub fn import_file_into_connection(connection: String, file_path: String, table_name: String, format: ImportFormat) -> anyhow::Result<String> {
+    import_file_into(connection, file_path, table_name, format)
+}
+
+// Round-trip a table to CSV/Parquet/JSON via `COPY ... TO`.
+pub fn export_table_to(table_name: String, out_path: String, format: ImportFormat) -> anyhow::Result<String> {
+    export_table(table_name, out_path, format)
+}
+
+// Same as `export_table_to`, but against a named connection instead of the
+// default one.
+pub fn export_table_from_connection(connection: String, table_name: String, out_path: String, format: ImportFormat) -> anyhow::Result<String> {
+    export_table_from(connection, table_name, out_path, format)
 }
 
 pub fn run_query(query: String) -> anyhow::Result<QueryResult> {
     execute_query(query)
 }
 
-pub fn get_all_tables() -> anyhow::Result<Vec<TableInfo>> {
-    get_tables_info()
+// Same as `run_query`, but against a named connection instead of the
+// default one — lets the UI compare two datasets side by side.
+pub fn run_query_on_connection(connection: String, query: String) -> anyhow::Result<QueryResult> {
+    execute_query_on(connection, query)
+}
+
+// Compatibility wrapper for bridge callers that have not migrated to the
+// typed `ColumnValue` rows in `QueryResult` yet.
+pub fn run_query_stringified(query: String) -> anyhow::Result<StringifiedQueryResult> {
+    execute_query_stringified(query)
+}
+
+// Parameterized query binding positional `?` placeholders, avoiding the
+// SQL-injection risk of interpolating values straight into `run_query`.
+pub fn run_query_with_parameters(query: String, params: Vec<ColumnValue>) -> anyhow::Result<QueryResult> {
+    run_query_with_params(query, params)
+}
+
+// Bulk insert/update helper: binds many rows against one cached statement
+// inside a single transaction, the main win for row-at-a-time ingestion.
+pub fn run_batch_with_parameters(query: String, rows: Vec<Vec<ColumnValue>>) -> anyhow::Result<String> {
+    execute_batch_params(query, rows)
 }
 
-pub fn get_all_indices() -> anyhow::Result<Vec<IndexInfo>> {
-    get_indices_info()
+pub fn get_database_info_summary() -> anyhow::Result<DatabaseInfo> {
+    get_database_info()
 }
 
-pub fn create_table_index(table_name: String, column_name: String) -> anyhow::Result<bool> {
+pub fn create_table_index(table_name: String, column_name: String) -> anyhow::Result<String> {
     create_index(table_name, column_name)
+}
+
+// Same as `create_table_index`, but against a named connection instead of
+// the default one.
+pub fn create_table_index_on_connection(connection: String, table_name: String, column_name: String) -> anyhow::Result<String> {
+    create_index_on(connection, table_name, column_name)
+}
+
+// Run DuckDB's `INSTALL`/`LOAD` for an extension, so `import_file_as`/
+// `run_query` can read formats or sources that extension brings in (e.g.
+// `httpfs` for `s3://`/`https://` URLs).
+pub fn load_database_extension(name: String) -> anyhow::Result<String> {
+    load_extension(name)
+}
+
+// Same as `load_database_extension`, but against a named connection instead
+// of the default one.
+pub fn load_database_extension_on_connection(connection: String, name: String) -> anyhow::Result<String> {
+    load_extension_on(connection, name)
+}
+
+// Convenience wrapper around `load_database_extension` for the common case
+// of wanting to read `s3://`/`https://` Parquet/CSV URLs directly.
+pub fn install_and_load_httpfs_extension() -> anyhow::Result<String> {
+    install_and_load_httpfs()
+}
+
+// Same as `install_and_load_httpfs_extension`, but against a named
+// connection instead of the default one.
+pub fn install_and_load_httpfs_on_connection(connection: String) -> anyhow::Result<String> {
+    install_and_load_httpfs_on(connection)
+}
+
+// Configure S3 credentials so cloud-hosted datasets can be queried without
+// pre-downloading them. Requires `httpfs` to already be loaded.
+pub fn configure_s3_credentials(region: String, key_id: String, secret: String) -> anyhow::Result<String> {
+    set_s3_credentials(region, key_id, secret)
+}
+
+// Same as `configure_s3_credentials`, but against a named connection
+// instead of the default one.
+pub fn configure_s3_credentials_on_connection(connection: String, region: String, key_id: String, secret: String) -> anyhow::Result<String> {
+    set_s3_credentials_on(connection, region, key_id, secret)
+}
+
+// Open a cursor over `query`, to be paged through with `fetch_query_rows`
+// instead of collecting the whole result set into memory up front — the
+// way to run large analytical queries without exhausting memory. Pages
+// are windowed `LIMIT`/`OFFSET` re-queries rather than a held-open
+// streaming cursor, so `query` must include its own `ORDER BY`.
+pub fn open_query_cursor(query: String, params: Vec<ColumnValue>) -> anyhow::Result<i64> {
+    open_cursor(query, params)
+}
+
+// Same as `open_query_cursor`, but against a named connection instead of
+// the default one.
+pub fn open_query_cursor_on_connection(connection: String, query: String, params: Vec<ColumnValue>) -> anyhow::Result<i64> {
+    open_cursor_on(connection, query, params)
+}
+
+// Fetch the next `batch_size` rows from a cursor opened with
+// `open_query_cursor`. Check `is_exhausted` on the returned batch to know
+// when to stop paging.
+pub fn fetch_query_rows(cursor_id: i64, batch_size: i64) -> anyhow::Result<CursorBatch> {
+    db_fetch_rows(cursor_id, batch_size)
+}
+
+// Close a cursor opened with `open_query_cursor`, freeing its paging state.
+pub fn close_query_cursor(cursor_id: i64) -> anyhow::Result<bool> {
+    db_close_cursor(cursor_id)
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 use anyhow::{anyhow, Result};
-use duckdb::{Connection, params};
+use duckdb::{params, params_from_iter, Connection, ToSql};
+use duckdb::types::{ToSqlOutput, Value};
 use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Mutex;
 use std::collections::HashMap;
 use std::time::{Instant, Duration};
@@ -14,177 +16,807 @@ pub struct DatabaseInfo {
     pub indices: HashMap<String, Vec<String>>,
 }
 
+// A single cell value, preserving the type DuckDB reported it as.
+// Mirrors the typed row-extraction approach used by FromRow-style drivers,
+// so the Flutter side can format numbers/dates/booleans instead of
+// re-parsing stringified cells.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Boolean(bool),
+    // Carries the `TimeUnit` DuckDB reported the value in (seconds,
+    // milliseconds, microseconds, or nanoseconds) alongside the raw tick
+    // count, so binding it back via `ToSql` doesn't have to guess.
+    Timestamp(duckdb::types::TimeUnit, i64),
+    Blob(Vec<u8>),
+}
+
+impl ColumnValue {
+    // Renders the value the way the old stringified API did, for callers
+    // that only need display text (e.g. `run_query_stringified`).
+    fn to_display_string(&self) -> String {
+        match self {
+            ColumnValue::Null => "NULL".to_string(),
+            ColumnValue::Integer(i) => i.to_string(),
+            ColumnValue::Real(f) => f.to_string(),
+            ColumnValue::Text(t) => t.clone(),
+            ColumnValue::Boolean(b) => b.to_string(),
+            ColumnValue::Timestamp(_, ts) => ts.to_string(),
+            ColumnValue::Blob(b) => format!("BLOB({})", b.len()),
+        }
+    }
+}
+
 // Structure to hold query results
 pub struct QueryResult {
+    pub column_names: Vec<String>,
+    pub column_types: Vec<String>,
+    pub rows: Vec<Vec<ColumnValue>>,
+    pub execution_time_ms: f64,
+    pub row_count: i64,
+}
+
+// Stringified mirror of `QueryResult`, kept for callers on the other side
+// of the bridge that were built against the old lossy API.
+pub struct StringifiedQueryResult {
     pub column_names: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub execution_time_ms: f64,
     pub row_count: i64,
 }
 
-// Static connection instance
-static DB_CONNECTION: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+impl ToSql for ColumnValue {
+    fn to_sql(&self) -> duckdb::Result<ToSqlOutput<'_>> {
+        let value = match self {
+            ColumnValue::Null => Value::Null,
+            ColumnValue::Integer(i) => Value::BigInt(*i),
+            ColumnValue::Real(f) => Value::Double(*f),
+            ColumnValue::Text(t) => Value::Text(t.clone()),
+            ColumnValue::Boolean(b) => Value::Boolean(*b),
+            ColumnValue::Timestamp(unit, ts) => Value::Timestamp(*unit, *ts),
+            ColumnValue::Blob(b) => Value::Blob(b.clone()),
+        };
+        Ok(ToSqlOutput::Owned(value))
+    }
+}
+
+// Name of the implicit connection used by every handle-less API (`run_query`,
+// `init_database`, ...), preserved for callers that only ever need one
+// database open at a time.
+const DEFAULT_CONNECTION: &str = "default";
+
+// Named connection registry. Each Flutter app can hold several independent
+// connections open at once (e.g. two Parquet datasets plus an in-memory
+// scratch DB) instead of serializing every call on a single global mutex.
+static DB_CONNECTIONS: Lazy<Mutex<HashMap<String, Connection>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn open_named_connection(name: &str, db_path: &str) -> Result<()> {
+    let conn = if db_path.is_empty() || db_path == ":memory:" {
+        Connection::open_in_memory()?
+    } else {
+        Connection::open(db_path)?
+    };
+    let mut connections = DB_CONNECTIONS.lock().map_err(|_| anyhow!("Failed to lock connection registry"))?;
+    connections.insert(name.to_string(), conn);
+    Ok(())
+}
+
+fn with_connection<T>(name: &str, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let connections = DB_CONNECTIONS.lock().map_err(|_| anyhow!("Failed to lock connection registry"))?;
+    let conn = connections
+        .get(name)
+        .ok_or_else(|| anyhow!("Connection '{}' is not open", name))?;
+    f(conn)
+}
+
+fn with_connection_mut<T>(name: &str, f: impl FnOnce(&mut Connection) -> Result<T>) -> Result<T> {
+    let mut connections = DB_CONNECTIONS.lock().map_err(|_| anyhow!("Failed to lock connection registry"))?;
+    let conn = connections
+        .get_mut(name)
+        .ok_or_else(|| anyhow!("Connection '{}' is not open", name))?;
+    f(conn)
+}
+
+// Open (or re-open) a named connection, persisting to `path` when one is
+// given. An empty path or the literal ":memory:" opens an in-memory
+// database instead, matching SQLite's convention for the same case.
+pub fn open_connection(name: String, path: String) -> Result<bool> {
+    open_named_connection(&name, &path)?;
+    Ok(true)
+}
+
+// Close a named connection, dropping it and any statement cache it holds.
+// Also invalidates any open cursors pointing at it, since they can no
+// longer page through a connection that's gone.
+pub fn close_connection(name: String) -> Result<bool> {
+    let mut connections = DB_CONNECTIONS.lock().map_err(|_| anyhow!("Failed to lock connection registry"))?;
+    let removed = connections.remove(&name).is_some();
+    drop(connections);
+    close_cursors_on(&name)?;
+    Ok(removed)
+}
 
-// Initialize DuckDB connection
-pub fn init_database() -> Result<String> {
-    let mut conn_guard = DB_CONNECTION.lock().map_err(|_| anyhow!("Failed to lock database connection"))?;
-    
-    // Create a new in-memory database
-    let conn = Connection::open_in_memory()?;
-    *conn_guard = Some(conn);
-    
+// Initialize the default connection, persisting to `db_path` when one is
+// given. Kept for callers that only ever need one database open at a time;
+// `open_connection` supports holding several named connections.
+pub fn init_database(db_path: String) -> Result<String> {
+    open_named_connection(DEFAULT_CONNECTION, &db_path)?;
     Ok("DuckDB initialized successfully".to_string())
 }
 
-// Import Parquet file
-pub fn import_parquet(file_path: String) -> Result<String> {
-    let mut conn_guard = DB_CONNECTION.lock().map_err(|_| anyhow!("Failed to lock database connection"))?;
-    
-    let conn = conn_guard.as_ref().ok_or_else(|| anyhow!("Database not initialized"))?;
-    
-    // Extract table name from file path
-    let file_name = std::path::Path::new(&file_path)
+// Result of `backup_database`/`restore_database`. DuckDB's C API has no
+// SQLite-style incremental page backup to report partial progress from —
+// `EXPORT DATABASE`/`IMPORT DATABASE` run to completion or return an
+// error — so this is always a single-shot, all-or-nothing result rather
+// than a series of updates; `done` is `true` on every `Ok`.
+pub struct BackupProgress {
+    pub done: bool,
+}
+
+// Snapshot the live database to the directory at `dst_path` using DuckDB's
+// `EXPORT DATABASE`, which writes a schema script plus one data file per
+// table. DuckDB's C API has no SQLite-style incremental page backup, so
+// this (or `ATTACH` + `COPY FROM DATABASE`) is the online-safe snapshot
+// primitive it actually exposes.
+pub fn backup_database(dst_path: String) -> Result<BackupProgress> {
+    with_connection(DEFAULT_CONNECTION, |conn| {
+        conn.execute_batch(&format!("EXPORT DATABASE '{}';", dst_path))?;
+        Ok(BackupProgress { done: true })
+    })
+}
+
+// Restore the live database from a directory written by `backup_database`,
+// via DuckDB's matching `IMPORT DATABASE`.
+pub fn restore_database(src_path: String) -> Result<BackupProgress> {
+    with_connection(DEFAULT_CONNECTION, |conn| {
+        conn.execute_batch(&format!("IMPORT DATABASE '{}';", src_path))?;
+        Ok(BackupProgress { done: true })
+    })
+}
+
+// File format for `import_file`/`export_table`. `Auto` is only valid for
+// imports, where it is resolved from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Parquet,
+    Csv,
+    Json,
+    NdJson,
+    Auto,
+}
+
+// Derive a safe table name from a file path the way the original
+// Parquet-only importer did (strip everything but alphanumerics/`_`).
+fn safe_table_name_from_path(file_path: &str) -> Result<String> {
+    let file_name = std::path::Path::new(file_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .ok_or_else(|| anyhow!("Invalid file path"))?;
-    
-    // Safe table name (remove special characters)
-    let table_name = file_name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
-    
-    // Create table from Parquet file
-    conn.execute_batch(&format!(
-        "CREATE TABLE {} AS SELECT * FROM read_parquet('{}');",
-        table_name, file_path
-    ))?;
-    
-    // Get row count
-    let mut stmt = conn.prepare(&format!("SELECT COUNT(*) FROM {}", table_name))?;
-    let row_count: i64 = stmt.query_row(params![], |row| row.get(0))?;
-    
-    Ok(format!("Imported {} rows into table {}", row_count, table_name))
-}
-
-// Execute SQL query
+    Ok(file_name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_"))
+}
+
+// Resolve `Auto` to a concrete format by looking at the file extension.
+fn resolve_import_format(file_path: &str, format: ImportFormat) -> Result<ImportFormat> {
+    if format != ImportFormat::Auto {
+        return Ok(format);
+    }
+    let extension = std::path::Path::new(file_path)
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match extension.as_str() {
+        "parquet" => Ok(ImportFormat::Parquet),
+        "csv" => Ok(ImportFormat::Csv),
+        "json" => Ok(ImportFormat::Json),
+        "ndjson" | "jsonl" => Ok(ImportFormat::NdJson),
+        _ => Err(anyhow!("Cannot auto-detect import format for '{}'", file_path)),
+    }
+}
+
+fn read_expr_for_format(file_path: &str, format: ImportFormat) -> String {
+    let file_path = escape_sql_literal(file_path);
+    match format {
+        ImportFormat::Parquet => format!("read_parquet('{}')", file_path),
+        ImportFormat::Csv => format!("read_csv_auto('{}')", file_path),
+        ImportFormat::Json => format!("read_json_auto('{}')", file_path),
+        ImportFormat::NdJson => format!("read_json_auto('{}', format='newline_delimited')", file_path),
+        ImportFormat::Auto => unreachable!("resolve_import_format must run first"),
+    }
+}
+
+// Import a Parquet, CSV, JSON, or newline-delimited-JSON file into a new
+// table on `connection`, dispatching to DuckDB's matching reader
+// (`read_parquet`, `read_csv_auto` or `read_json_auto`). Pass `ImportFormat::Auto` to pick the reader from the file
+// extension.
+pub fn import_file_into(connection: String, file_path: String, table_name: String, format: ImportFormat) -> Result<String> {
+    with_connection(&connection, |conn| {
+        let resolved_format = resolve_import_format(&file_path, format)?;
+        let table_name = table_name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+        let read_expr = read_expr_for_format(&file_path, resolved_format);
+
+        conn.execute_batch(&format!(
+            "CREATE TABLE {} AS SELECT * FROM {};",
+            table_name, read_expr
+        ))?;
+
+        let mut stmt = conn.prepare(&format!("SELECT COUNT(*) FROM {}", table_name))?;
+        let row_count: i64 = stmt.query_row(params![], |row| row.get(0))?;
+
+        Ok(format!("Imported {} rows into table {}", row_count, table_name))
+    })
+}
+
+// Compatibility wrapper over `import_file_into` for callers that only use
+// the default connection.
+pub fn import_file(file_path: String, table_name: String, format: ImportFormat) -> Result<String> {
+    import_file_into(DEFAULT_CONNECTION.to_string(), file_path, table_name, format)
+}
+
+// Compatibility wrapper over `import_file` for existing Parquet-only
+// callers, which derive the table name from the file path themselves.
+pub fn import_parquet(file_path: String) -> Result<String> {
+    let table_name = safe_table_name_from_path(&file_path)?;
+    import_file(file_path, table_name, ImportFormat::Parquet)
+}
+
+// Round-trip a table on `connection` back out to CSV/Parquet/JSON via
+// `COPY ... TO`.
+pub fn export_table_from(connection: String, table_name: String, out_path: String, format: ImportFormat) -> Result<String> {
+    with_connection(&connection, |conn| {
+        let table_name = table_name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+        let copy_options = match format {
+            ImportFormat::Parquet => "(FORMAT PARQUET)",
+            ImportFormat::Csv => "(FORMAT CSV, HEADER)",
+            ImportFormat::Json => "(FORMAT JSON, ARRAY true)",
+            ImportFormat::NdJson => "(FORMAT JSON, ARRAY false)",
+            ImportFormat::Auto => return Err(anyhow!("export_table requires an explicit format")),
+        };
+
+        conn.execute_batch(&format!(
+            "COPY {} TO '{}' {};",
+            table_name, escape_sql_literal(&out_path), copy_options
+        ))?;
+
+        Ok(format!("Exported table {} to {}", table_name, out_path))
+    })
+}
+
+// Compatibility wrapper over `export_table_from` for callers that only use
+// the default connection.
+pub fn export_table(table_name: String, out_path: String, format: ImportFormat) -> Result<String> {
+    export_table_from(DEFAULT_CONNECTION.to_string(), table_name, out_path, format)
+}
+
+// DuckDB extension names are a fixed, known vocabulary (httpfs, json,
+// parquet, aws, ...), so unlike `table_name` there's no legitimate reason
+// for one to contain anything but identifier characters. Reject instead
+// of mangling, since silently rewriting the name would just load a
+// different (likely nonexistent) extension.
+fn validate_extension_name(name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(anyhow!(
+            "Invalid extension name '{}': only alphanumeric characters and underscores are allowed",
+            name
+        ));
+    }
+    Ok(())
+}
+
+// Run DuckDB's `INSTALL`/`LOAD` for an extension on a named connection, so
+// `import_file`/`run_query` can read formats or sources that extension
+// brings in (e.g. `httpfs` for `s3://`/`https://` URLs).
+pub fn load_extension_on(connection: String, name: String) -> Result<String> {
+    validate_extension_name(&name)?;
+    with_connection(&connection, |conn| {
+        conn.execute_batch(&format!("INSTALL {}; LOAD {};", name, name))?;
+        Ok(format!("Loaded extension {}", name))
+    })
+}
+
+// Compatibility wrapper over `load_extension_on` for callers that only use
+// the default connection.
+pub fn load_extension(name: String) -> Result<String> {
+    load_extension_on(DEFAULT_CONNECTION.to_string(), name)
+}
+
+// Convenience wrapper around `load_extension_on` for the common case of
+// wanting to read `s3://`/`https://` Parquet/CSV URLs directly.
+pub fn install_and_load_httpfs_on(connection: String) -> Result<String> {
+    load_extension_on(connection, "httpfs".to_string())
+}
+
+// Compatibility wrapper over `install_and_load_httpfs_on` for callers that
+// only use the default connection.
+pub fn install_and_load_httpfs() -> Result<String> {
+    install_and_load_httpfs_on(DEFAULT_CONNECTION.to_string())
+}
+
+// Escape a value for embedding inside a single-quoted SQL string literal:
+// double any embedded `'`, the standard SQL string-literal escape. Used
+// for file paths and S3 credentials, which (unlike table/column names)
+// can legitimately contain characters an identifier allowlist would
+// reject, e.g. `/`, `+`, `=` in an S3 secret key.
+fn escape_sql_literal(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+// Issue the `SET s3_*` statements needed to query a cloud-hosted dataset
+// without pre-downloading it. Requires `httpfs` to already be loaded on
+// `connection`.
+pub fn set_s3_credentials_on(connection: String, region: String, key_id: String, secret: String) -> Result<String> {
+    let region = escape_sql_literal(&region);
+    let key_id = escape_sql_literal(&key_id);
+    let secret = escape_sql_literal(&secret);
+    with_connection(&connection, |conn| {
+        conn.execute_batch(&format!(
+            "SET s3_region='{}'; SET s3_access_key_id='{}'; SET s3_secret_access_key='{}';",
+            region, key_id, secret
+        ))?;
+        Ok("S3 credentials configured".to_string())
+    })
+}
+
+// Compatibility wrapper over `set_s3_credentials_on` for callers that only
+// use the default connection.
+pub fn set_s3_credentials(region: String, key_id: String, secret: String) -> Result<String> {
+    set_s3_credentials_on(DEFAULT_CONNECTION.to_string(), region, key_id, secret)
+}
+
+// Convert a single cell into its typed representation. DuckDB reports
+// integers at their native width (`TinyInt` through `HugeInt`, plus the
+// unsigned variants) rather than a single `Integer` case, so every width
+// is widened into `ColumnValue::Integer(i64)`; `HugeInt`/`UHugeInt`/`UBigInt`
+// can exceed `i64::MAX` and saturate on overflow rather than panicking or
+// silently wrapping.
+fn column_value_from_ref(val: duckdb::types::ValueRef) -> ColumnValue {
+    match val {
+        duckdb::types::ValueRef::Null => ColumnValue::Null,
+        duckdb::types::ValueRef::TinyInt(i) => ColumnValue::Integer(i as i64),
+        duckdb::types::ValueRef::SmallInt(i) => ColumnValue::Integer(i as i64),
+        duckdb::types::ValueRef::Int(i) => ColumnValue::Integer(i as i64),
+        duckdb::types::ValueRef::BigInt(i) => ColumnValue::Integer(i),
+        duckdb::types::ValueRef::HugeInt(i) => ColumnValue::Integer(i.clamp(i64::MIN as i128, i64::MAX as i128) as i64),
+        duckdb::types::ValueRef::UTinyInt(i) => ColumnValue::Integer(i as i64),
+        duckdb::types::ValueRef::USmallInt(i) => ColumnValue::Integer(i as i64),
+        duckdb::types::ValueRef::UInt(i) => ColumnValue::Integer(i as i64),
+        duckdb::types::ValueRef::UBigInt(i) => ColumnValue::Integer(i.min(i64::MAX as u64) as i64),
+        duckdb::types::ValueRef::UHugeInt(i) => ColumnValue::Integer(i.min(i64::MAX as u128) as i64),
+        duckdb::types::ValueRef::Float(f) => ColumnValue::Real(f as f64),
+        duckdb::types::ValueRef::Double(f) => ColumnValue::Real(f),
+        duckdb::types::ValueRef::Text(t) => ColumnValue::Text(String::from_utf8_lossy(t).to_string()),
+        duckdb::types::ValueRef::Boolean(b) => ColumnValue::Boolean(b),
+        duckdb::types::ValueRef::Timestamp(unit, t) => ColumnValue::Timestamp(unit, t),
+        duckdb::types::ValueRef::Blob(b) => ColumnValue::Blob(b.to_vec()),
+        // Any other DuckDB type (dates, decimals, lists, structs, ...) falls
+        // back to its textual form rather than failing the whole query.
+        other => ColumnValue::Text(format!("{:?}", other)),
+    }
+}
+
+// Name of the type a fetched cell actually came back as. `Statement::column_type`
+// reports the *declared* type before the statement has been stepped, which for
+// some expressions/empty results doesn't match what's actually returned, so
+// callers derive `column_types` from a real row's cells instead wherever one
+// is available.
+fn column_value_type_name(value: &ColumnValue) -> String {
+    match value {
+        ColumnValue::Null => "Null".to_string(),
+        ColumnValue::Integer(_) => "Integer".to_string(),
+        ColumnValue::Real(_) => "Real".to_string(),
+        ColumnValue::Text(_) => "Text".to_string(),
+        ColumnValue::Boolean(_) => "Boolean".to_string(),
+        ColumnValue::Timestamp(_, _) => "Timestamp".to_string(),
+        ColumnValue::Blob(_) => "Blob".to_string(),
+    }
+}
+
+// Execute a SQL query against a named connection.
+pub fn execute_query_on(connection: String, query: String) -> Result<QueryResult> {
+    with_connection(&connection, |conn| {
+        // Measure execution time
+        let start = Instant::now();
+        let mut stmt = conn.prepare(&query)?;
+
+        // Get column names
+        let column_names: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // Execute query and collect results
+        let rows_result = stmt.query_map(params![], |row| {
+            let mut row_data = Vec::new();
+            for i in 0..row.as_ref().column_count() {
+                let value = match row.get_ref(i) {
+                    Ok(val) => column_value_from_ref(val),
+                    Err(_) => ColumnValue::Text("ERROR".to_string()),
+                };
+                row_data.push(value);
+            }
+            Ok(row_data)
+        })?;
+
+        let mut rows = Vec::new();
+        let mut row_count = 0;
+        for row in rows_result {
+            rows.push(row?);
+            row_count += 1;
+        }
+
+        // Derive column types from the first fetched row, since
+        // `stmt.column_type` only reports the declared type of an
+        // unstepped statement. Fall back to that declared type when the
+        // result has no rows to inspect.
+        let column_types: Vec<String> = match rows.first() {
+            Some(first_row) => first_row.iter().map(column_value_type_name).collect(),
+            None => (0..column_names.len())
+                .map(|i| format!("{:?}", stmt.column_type(i)))
+                .collect(),
+        };
+
+        let duration = start.elapsed();
+
+        Ok(QueryResult {
+            column_names,
+            column_types,
+            rows,
+            execution_time_ms: duration.as_secs_f64() * 1000.0,
+            row_count,
+        })
+    })
+}
+
+// Compatibility wrapper over `execute_query_on` for callers that only use
+// the default connection.
 pub fn execute_query(query: String) -> Result<QueryResult> {
-    let conn_guard = DB_CONNECTION.lock().map_err(|_| anyhow!("Failed to lock database connection"))?;
-    let conn = conn_guard.as_ref().ok_or_else(|| anyhow!("Database not initialized"))?;
-    
-    // Measure execution time
-    let start = Instant::now();
-    let mut stmt = conn.prepare(&query)?;
-    
-    // Get column names
-    let column_names: Vec<String> = stmt
-        .column_names()
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect();
-    
-    // Execute query and collect results
-    let rows_result = stmt.query_map(params![], |row| {
-        let mut row_data = Vec::new();
-        for i in 0..row.column_count() {
-            let value: String = match row.get_ref(i) {
-                Ok(val) => {
-                    match val {
-                        duckdb::types::ValueRef::Null => "NULL".to_string(),
-                        duckdb::types::ValueRef::Integer(i) => i.to_string(),
-                        duckdb::types::ValueRef::Real(f) => f.to_string(),
-                        duckdb::types::ValueRef::Text(t) => String::from_utf8_lossy(t).to_string(),
-                        duckdb::types::ValueRef::Blob(b) => format!("BLOB({})", b.len()),
-                    }
-                },
-                Err(_) => "ERROR".to_string(),
-            };
-            row_data.push(value);
+    execute_query_on(DEFAULT_CONNECTION.to_string(), query)
+}
+
+// Stringified compatibility wrapper over `execute_query` for bridge callers
+// that have not migrated to typed `ColumnValue` rows yet.
+pub fn execute_query_stringified(query: String) -> Result<StringifiedQueryResult> {
+    let typed = execute_query(query)?;
+    Ok(StringifiedQueryResult {
+        column_names: typed.column_names,
+        rows: typed
+            .rows
+            .into_iter()
+            .map(|row| row.iter().map(ColumnValue::to_display_string).collect())
+            .collect(),
+        execution_time_ms: typed.execution_time_ms,
+        row_count: typed.row_count,
+    })
+}
+
+// Execute a parameterized SQL query, binding positional `?` placeholders
+// from `params`. Uses `Connection::prepare_cached` so repeated calls with
+// the same SQL text (the common case for row-at-a-time ingestion) skip
+// re-preparation — DuckDB keeps the cached statement keyed by SQL text
+// internally, the same trick SQLite wrappers use with `CachedStatement`.
+pub fn run_query_with_params(query: String, query_params: Vec<ColumnValue>) -> Result<QueryResult> {
+    with_connection(DEFAULT_CONNECTION, |conn| {
+        let start = Instant::now();
+        let mut stmt = conn.prepare_cached(&query)?;
+
+        let column_names: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let rows_result = stmt.query_map(params_from_iter(query_params.iter()), |row| {
+            let mut row_data = Vec::new();
+            for i in 0..row.as_ref().column_count() {
+                let value = match row.get_ref(i) {
+                    Ok(val) => column_value_from_ref(val),
+                    Err(_) => ColumnValue::Text("ERROR".to_string()),
+                };
+                row_data.push(value);
+            }
+            Ok(row_data)
+        })?;
+
+        let mut rows = Vec::new();
+        let mut row_count = 0;
+        for row in rows_result {
+            rows.push(row?);
+            row_count += 1;
         }
-        Ok(row_data)
-    })?;
-    
-    let mut rows = Vec::new();
-    let mut row_count = 0;
-    for row in rows_result {
-        rows.push(row?);
-        row_count += 1;
-    }
-    
-    let duration = start.elapsed();
-    
-    Ok(QueryResult {
-        column_names,
-        rows,
-        execution_time_ms: duration.as_secs_f64() * 1000.0,
-        row_count,
+
+        // See `execute_query_on`: the declared statement type is only a
+        // fallback for empty results, not the source of truth.
+        let column_types: Vec<String> = match rows.first() {
+            Some(first_row) => first_row.iter().map(column_value_type_name).collect(),
+            None => (0..column_names.len())
+                .map(|i| format!("{:?}", stmt.column_type(i)))
+                .collect(),
+        };
+
+        let duration = start.elapsed();
+
+        Ok(QueryResult {
+            column_names,
+            column_types,
+            rows,
+            execution_time_ms: duration.as_secs_f64() * 1000.0,
+            row_count,
+        })
+    })
+}
+
+// Bind many rows against one cached statement inside a single transaction.
+// This is the main performance win for row-at-a-time ingestion, since it
+// avoids both re-preparing the statement and committing once per row.
+pub fn execute_batch_params(query: String, rows: Vec<Vec<ColumnValue>>) -> Result<String> {
+    with_connection_mut(DEFAULT_CONNECTION, |conn| {
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare_cached(&query)?;
+            for row in &rows {
+                stmt.execute(params_from_iter(row.iter()))?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(format!("Inserted {} rows", rows.len()))
     })
 }
 
 // Get database information
 pub fn get_database_info() -> Result<DatabaseInfo> {
-    let conn_guard = DB_CONNECTION.lock().map_err(|_| anyhow!("Failed to lock database connection"))?;
-    let conn = conn_guard.as_ref().ok_or_else(|| anyhow!("Database not initialized"))?;
-    
-    // Get list of tables
-    let mut tables_stmt = conn.prepare("SELECT name FROM sqlite_master WHERE type='table'")?;
-    let tables_rows = tables_stmt.query_map(params![], |row| row.get::<_, String>(0))?;
-    
-    let mut table_count = 0;
-    let mut row_count = HashMap::new();
-    let mut table_schemas = HashMap::new();
-    let mut indices = HashMap::new();
-    
-    for table_result in tables_rows {
-        let table_name = table_result?;
-        table_count += 1;
-        
-        // Get row count for each table
-        let mut count_stmt = conn.prepare(&format!("SELECT COUNT(*) FROM {}", table_name))?;
-        let count: i64 = count_stmt.query_row(params![], |row| row.get(0))?;
-        row_count.insert(table_name.clone(), count);
-        
-        // Get schema for each table
-        let mut schema_stmt = conn.prepare(&format!("PRAGMA table_info({})", table_name))?;
-        let schema_rows = schema_stmt.query_map(params![], |row| {
-            let name: String = row.get(1)?;
-            let type_str: String = row.get(2)?;
-            Ok(format!("{} {}", name, type_str))
-        })?;
-        
-        let mut schema_vec = Vec::new();
-        for schema_row in schema_rows {
-            schema_vec.push(schema_row?);
+    with_connection(DEFAULT_CONNECTION, |conn| {
+        // Get list of tables
+        let mut tables_stmt = conn.prepare("SELECT name FROM sqlite_master WHERE type='table'")?;
+        let tables_rows = tables_stmt.query_map(params![], |row| row.get::<_, String>(0))?;
+
+        let mut table_count = 0;
+        let mut row_count = HashMap::new();
+        let mut table_schemas = HashMap::new();
+        let mut indices = HashMap::new();
+
+        for table_result in tables_rows {
+            let table_name = table_result?;
+            table_count += 1;
+
+            // Get row count for each table
+            let mut count_stmt = conn.prepare(&format!("SELECT COUNT(*) FROM {}", table_name))?;
+            let count: i64 = count_stmt.query_row(params![], |row| row.get(0))?;
+            row_count.insert(table_name.clone(), count);
+
+            // Get schema for each table
+            let mut schema_stmt = conn.prepare(&format!("PRAGMA table_info({})", table_name))?;
+            let schema_rows = schema_stmt.query_map(params![], |row| {
+                let name: String = row.get(1)?;
+                let type_str: String = row.get(2)?;
+                Ok(format!("{} {}", name, type_str))
+            })?;
+
+            let mut schema_vec = Vec::new();
+            for schema_row in schema_rows {
+                schema_vec.push(schema_row?);
+            }
+            let schema = schema_vec.join(", ");
+            table_schemas.insert(table_name.clone(), schema);
+
+            // Get indices for each table
+            let mut index_stmt = conn.prepare(&format!("PRAGMA index_list({})", table_name))?;
+            let index_rows = index_stmt.query_map(params![], |row| row.get::<_, String>(1))?;
+
+            let mut index_vec = Vec::new();
+            for index_row in index_rows {
+                index_vec.push(index_row?);
+            }
+            indices.insert(table_name.clone(), index_vec);
         }
-        let schema = schema_vec.join(", ");
-        table_schemas.insert(table_name.clone(), schema);
-        
-        // Get indices for each table
-        let mut index_stmt = conn.prepare(&format!("PRAGMA index_list({})", table_name))?;
-        let index_rows = index_stmt.query_map(params![], |row| row.get::<_, String>(1))?;
-        
-        let mut index_vec = Vec::new();
-        for index_row in index_rows {
-            index_vec.push(index_row?);
+
+        Ok(DatabaseInfo {
+            table_count,
+            row_count,
+            table_schemas,
+            indices,
+        })
+    })
+}
+
+// Create an index on a table within a named connection.
+pub fn create_index_on(connection: String, table_name: String, column_name: String) -> Result<String> {
+    with_connection(&connection, |conn| {
+        let table_name = table_name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+        let column_name = column_name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+        let index_name = format!("idx_{}_{}", table_name, column_name);
+        conn.execute_batch(&format!(
+            "CREATE INDEX {} ON {} ({});",
+            index_name, table_name, column_name
+        ))?;
+
+        Ok(format!("Created index {} on {}.{}", index_name, table_name, column_name))
+    })
+}
+
+// Compatibility wrapper over `create_index_on` for callers that only use
+// the default connection.
+pub fn create_index(table_name: String, column_name: String) -> Result<String> {
+    create_index_on(DEFAULT_CONNECTION.to_string(), table_name, column_name)
+}
+
+// A page of rows fetched from an open cursor, mirroring `QueryResult` but
+// with an `is_exhausted` flag so the Flutter side knows when to stop
+// paging instead of eagerly collecting the whole result set up front.
+pub struct CursorBatch {
+    pub column_names: Vec<String>,
+    pub column_types: Vec<String>,
+    pub rows: Vec<Vec<ColumnValue>>,
+    pub row_count: i64,
+    pub is_exhausted: bool,
+}
+
+// An in-flight query, re-run a page at a time via `LIMIT`/`OFFSET` rather
+// than held open as a live `Statement`/`Rows` borrow, since a cursor must
+// outlive the single FFI call that created it (duckdb-rs statements and
+// rows borrow from their `Connection`, and the registry can't hold a
+// borrow across calls without unsafe self-referential storage).
+//
+// This makes `fetch_rows` windowed re-querying, NOT a true streaming
+// cursor: each page re-executes `query` from scratch. Without a
+// deterministic row order, DuckDB gives no guarantee that re-execution
+// (especially parallelized) revisits rows in the same order, so pages
+// could skip or repeat rows. `open_cursor_on` requires `query` to carry
+// its own `ORDER BY` for exactly this reason — don't relax that check.
+struct OpenCursor {
+    connection: String,
+    query: String,
+    query_params: Vec<ColumnValue>,
+    offset: i64,
+    exhausted: bool,
+}
+
+static NEXT_CURSOR_ID: AtomicI64 = AtomicI64::new(1);
+static CURSORS: Lazy<Mutex<HashMap<i64, OpenCursor>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// A crude top-level `ORDER BY` check: good enough to catch the common
+// mistake of paging an unordered query, not a real SQL parser. It also
+// accepts a query whose only `ORDER BY` sits inside a sub-select or a
+// window clause, even though the outer result order is then not fixed.
+fn has_order_by(query: &str) -> bool {
+    query.to_ascii_lowercase().contains("order by")
+}
+
+// Open a cursor over `query` against a named connection, to be paged
+// through with `fetch_rows`. The query itself isn't run until the first
+// `fetch_rows` call. `fetch_rows` re-runs `query` with `LIMIT`/`OFFSET` on
+// every page rather than holding a live statement open (see `OpenCursor`),
+// so `query` MUST carry its own `ORDER BY` — without one, DuckDB doesn't
+// guarantee repeated executions return rows in the same order, and pages
+// could silently skip or duplicate rows.
+pub fn open_cursor_on(connection: String, query: String, query_params: Vec<ColumnValue>) -> Result<i64> {
+    if !has_order_by(&query) {
+        return Err(anyhow!(
+            "open_cursor requires `query` to include an ORDER BY: fetch_rows pages via \
+             LIMIT/OFFSET re-execution, which is only safe to paginate with a deterministic order"
+        ));
+    }
+
+    // Fail fast if the connection doesn't exist, rather than deferring the
+    // error to the first `fetch_rows` call.
+    with_connection(&connection, |_| Ok(()))?;
+
+    let cursor_id = NEXT_CURSOR_ID.fetch_add(1, Ordering::SeqCst);
+    let mut cursors = CURSORS.lock().map_err(|_| anyhow!("Failed to lock cursor registry"))?;
+    cursors.insert(cursor_id, OpenCursor {
+        connection,
+        query,
+        query_params,
+        offset: 0,
+        exhausted: false,
+    });
+    Ok(cursor_id)
+}
+
+// Compatibility wrapper over `open_cursor_on` for callers that only use
+// the default connection.
+pub fn open_cursor(query: String, query_params: Vec<ColumnValue>) -> Result<i64> {
+    open_cursor_on(DEFAULT_CONNECTION.to_string(), query, query_params)
+}
+
+// Fetch the next `batch_size` rows from a cursor opened with `open_cursor`,
+// re-running the underlying query with `LIMIT`/`OFFSET` so only one page
+// is ever materialized in memory at a time. This is windowed re-querying,
+// not a held-open streaming cursor, which is why `open_cursor` requires a
+// deterministic `ORDER BY` on the underlying query. Returns an empty,
+// exhausted batch once the query has no more rows.
+pub fn fetch_rows(cursor_id: i64, batch_size: i64) -> Result<CursorBatch> {
+    let (connection, query, query_params, offset, already_exhausted) = {
+        let cursors = CURSORS.lock().map_err(|_| anyhow!("Failed to lock cursor registry"))?;
+        let cursor = cursors
+            .get(&cursor_id)
+            .ok_or_else(|| anyhow!("Cursor {} is not open", cursor_id))?;
+        (cursor.connection.clone(), cursor.query.clone(), cursor.query_params.clone(), cursor.offset, cursor.exhausted)
+    };
+
+    if already_exhausted {
+        return Ok(CursorBatch {
+            column_names: Vec::new(),
+            column_types: Vec::new(),
+            rows: Vec::new(),
+            row_count: 0,
+            is_exhausted: true,
+        });
+    }
+
+    let paged_query = format!("SELECT * FROM ({}) AS cursor_page LIMIT {} OFFSET {}", query, batch_size, offset);
+
+    let batch = with_connection(&connection, |conn| {
+        let mut stmt = conn.prepare(&paged_query)?;
+
+        let column_names: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let rows_result = stmt.query_map(params_from_iter(query_params.iter()), |row| {
+            let mut row_data = Vec::new();
+            for i in 0..row.as_ref().column_count() {
+                let value = match row.get_ref(i) {
+                    Ok(val) => column_value_from_ref(val),
+                    Err(_) => ColumnValue::Text("ERROR".to_string()),
+                };
+                row_data.push(value);
+            }
+            Ok(row_data)
+        })?;
+
+        let mut rows = Vec::new();
+        for row in rows_result {
+            rows.push(row?);
         }
-        indices.insert(table_name.clone(), index_vec);
+
+        // See `execute_query_on`: the declared statement type is only a
+        // fallback for empty results, not the source of truth.
+        let column_types: Vec<String> = match rows.first() {
+            Some(first_row) => first_row.iter().map(column_value_type_name).collect(),
+            None => (0..column_names.len())
+                .map(|i| format!("{:?}", stmt.column_type(i)))
+                .collect(),
+        };
+
+        Ok((column_names, column_types, rows))
+    })?;
+
+    let (column_names, column_types, rows) = batch;
+    let row_count = rows.len() as i64;
+    let is_exhausted = row_count < batch_size;
+
+    let mut cursors = CURSORS.lock().map_err(|_| anyhow!("Failed to lock cursor registry"))?;
+    if let Some(cursor) = cursors.get_mut(&cursor_id) {
+        cursor.offset += row_count;
+        cursor.exhausted = is_exhausted;
     }
-    
-    Ok(DatabaseInfo {
-        table_count,
+
+    Ok(CursorBatch {
+        column_names,
+        column_types,
+        rows,
         row_count,
-        table_schemas,
-        indices,
+        is_exhausted,
     })
 }
 
-// Create index on a table
-pub fn create_index(table_name: String, column_name: String) -> Result<String> {
-    let conn_guard = DB_CONNECTION.lock().map_err(|_| anyhow!("Failed to lock database connection"))?;
-    let conn = conn_guard.as_ref().ok_or_else(|| anyhow!("Database not initialized"))?;
-    
-    let index_name = format!("idx_{}_{}", table_name, column_name);
-    conn.execute_batch(&format!(
-        "CREATE INDEX {} ON {} ({});",
-        index_name, table_name, column_name
-    ))?;
-    
-    Ok(format!("Created index {} on {}.{}", index_name, table_name, column_name))
+// Close a cursor opened with `open_cursor`, freeing its paging state.
+pub fn close_cursor(cursor_id: i64) -> Result<bool> {
+    let mut cursors = CURSORS.lock().map_err(|_| anyhow!("Failed to lock cursor registry"))?;
+    Ok(cursors.remove(&cursor_id).is_some())
+}
+
+// Invalidate every cursor open against `connection`, used when the
+// connection itself is closed out from under them.
+fn close_cursors_on(connection: &str) -> Result<()> {
+    let mut cursors = CURSORS.lock().map_err(|_| anyhow!("Failed to lock cursor registry"))?;
+    cursors.retain(|_, cursor| cursor.connection != connection);
+    Ok(())
 }
\ No newline at end of file